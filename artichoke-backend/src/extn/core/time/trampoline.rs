@@ -1,5 +1,11 @@
 //! Glue between mruby FFI and `Time` Rust implementation.
 
+mod convert;
+mod directives;
+mod format;
+mod parse;
+mod relative;
+
 use spinoso_time::MICROS_IN_NANO;
 
 use crate::convert::{implicitly_convert_to_int, implicitly_convert_to_string};
@@ -8,6 +14,7 @@ use crate::extn::core::time::{Offset, Time};
 use crate::extn::prelude::*;
 
 const MAX_NANOS: i64 = 1_000_000_000 - 1;
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
 
 // Generate a subsecond multiplier from the given ruby value
 //
@@ -31,6 +38,44 @@ fn subsec_multiplier(interp: &mut Artichoke, subsec_type: Option<Value>) -> Resu
     }
 }
 
+// Splits a Ruby Integer or Float number of seconds into a whole-seconds and signed
+// nanosecond-remainder pair, as required by `Time#+` and `Time#-`.
+fn seconds_and_nanos_from_numeric(interp: &mut Artichoke, value: Value) -> Result<(i64, i64), Error> {
+    match value.ruby_type() {
+        Ruby::Fixnum => {
+            let seconds = implicitly_convert_to_int(interp, value)?;
+            Ok((seconds, 0))
+        }
+        Ruby::Float => {
+            let seconds: f64 = interp.try_convert(value)?;
+            let whole = seconds.trunc();
+            let nanos = (seconds - whole) * NANOS_PER_SECOND as f64;
+            Ok((whole as i64, nanos.round() as i64))
+        }
+        _ => Err(TypeError::with_message("can't convert into an exact number").into()),
+    }
+}
+
+// Adds a signed `(seconds, nanos)` duration to `time`'s timespec with checked arithmetic,
+// normalizing the nanosecond remainder back into `0..=999_999_999` and carrying into the whole
+// seconds. Shared by `Time#+` and the numeric branch of `Time#-`.
+fn time_plus_duration(time: &Time, add_seconds: i64, add_nanos: i64) -> Result<Time, Error> {
+    let total_nanos = i64::from(time.nanoseconds())
+        .checked_add(add_nanos)
+        .ok_or_else(|| ArgumentError::with_message("Time too large"))?;
+    let carry_seconds = total_nanos.div_euclid(NANOS_PER_SECOND);
+    let nanos = total_nanos.rem_euclid(NANOS_PER_SECOND);
+
+    let seconds = time
+        .to_int()
+        .checked_add(add_seconds)
+        .and_then(|seconds| seconds.checked_add(carry_seconds))
+        .ok_or_else(|| ArgumentError::with_message("Time too large"))?;
+
+    Time::with_timespec_and_offset(seconds, nanos as u32, time.offset())
+        .map_err(|_| ArgumentError::with_message("Time too large").into())
+}
+
 fn offset_from_options(interp: &mut Artichoke, options: Value) -> Result<Offset, Error> {
     let hash: Vec<(Value, Value)> = interp.try_convert_mut(options)?;
     let tz = hash
@@ -138,6 +183,25 @@ where
     Err(NotImplementedError::new().into())
 }
 
+pub fn strptime(interp: &mut Artichoke, mut string: Value, mut format: Value) -> Result<Value, Error> {
+    let string = unsafe { implicitly_convert_to_string(interp, &mut string)? };
+    let format = unsafe { implicitly_convert_to_string(interp, &mut format)? };
+    let time = parse::strptime(string, format)?.into_time()?;
+    Time::alloc_value(time, interp)
+}
+
+pub fn parse_from_rfc2822(interp: &mut Artichoke, mut string: Value) -> Result<Value, Error> {
+    let string = unsafe { implicitly_convert_to_string(interp, &mut string)? };
+    let time = parse::parse_rfc2822(string)?.into_time()?;
+    Time::alloc_value(time, interp)
+}
+
+pub fn parse_from_rfc3339(interp: &mut Artichoke, mut string: Value) -> Result<Value, Error> {
+    let string = unsafe { implicitly_convert_to_string(interp, &mut string)? };
+    let time = parse::parse_rfc3339(string)?.into_time()?;
+    Time::alloc_value(time, interp)
+}
+
 pub fn mktime<T>(interp: &mut Artichoke, args: T) -> Result<Value, Error>
 where
     T: IntoIterator<Item = Value>,
@@ -147,6 +211,16 @@ where
     Err(NotImplementedError::new().into())
 }
 
+pub fn parse_relative(interp: &mut Artichoke, mut string: Value, base: Option<Value>) -> Result<Value, Error> {
+    let string = unsafe { implicitly_convert_to_string(interp, &mut string)? };
+    let base = match base {
+        Some(mut base) => *unsafe { Time::unbox_from_value(&mut base, interp)? },
+        None => Time::now().map_err(|_| StandardError::with_message("now is not available"))?,
+    };
+    let time = relative::parse_relative(string, &base)?;
+    Time::alloc_value(time, interp)
+}
+
 // Core
 
 pub fn to_int(interp: &mut Artichoke, mut time: Value) -> Result<Value, Error> {
@@ -257,62 +331,80 @@ pub fn to_string(interp: &mut Artichoke, mut time: Value) -> Result<Value, Error
     interp.try_convert_mut(time.to_string())
 }
 
-pub fn to_array(interp: &mut Artichoke, time: Value) -> Result<Value, Error> {
-    // Need to implement `Convert` for timezone offset.
-    let _ = interp;
-    let _ = time;
-    Err(NotImplementedError::new().into())
+pub fn to_array(interp: &mut Artichoke, mut time: Value) -> Result<Value, Error> {
+    let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
+
+    let fields = vec![
+        interp.convert(time.second()),
+        interp.convert(time.minute()),
+        interp.convert(time.hour()),
+        interp.convert(time.day()),
+        interp.convert(time.month()),
+        interp.convert(time.year()),
+        interp.convert(time.day_of_week()),
+        interp.convert(time.day_of_year()),
+        interp.convert(time.is_dst()),
+        interp.try_convert_mut(convert::designation(&time))?,
+    ];
+    interp.try_convert_mut(fields)
 }
 
 // Math
 
-pub fn plus(interp: &mut Artichoke, time: Value, other: Value) -> Result<Value, Error> {
-    let _ = interp;
-    let _ = time;
-    let _ = other;
-    Err(NotImplementedError::new().into())
+pub fn plus(interp: &mut Artichoke, mut time: Value, other: Value) -> Result<Value, Error> {
+    let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
+    let (add_seconds, add_nanos) = seconds_and_nanos_from_numeric(interp, other)?;
+    let result = time_plus_duration(&time, add_seconds, add_nanos)?;
+    Time::alloc_value(result, interp)
 }
 
-pub fn minus(interp: &mut Artichoke, time: Value, other: Value) -> Result<Value, Error> {
-    let _ = interp;
-    let _ = time;
-    let _ = other;
-    Err(NotImplementedError::new().into())
+pub fn minus(interp: &mut Artichoke, mut time: Value, mut other: Value) -> Result<Value, Error> {
+    let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
 
-    //let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
-    //let other = if let Ok(other) = unsafe { Time::unbox_from_value(&mut other, interp) } {
-    //other
-    //} else if let Ok(other) = implicitly_convert_to_int(interp, other) {
-    //let _ = other;
-    //return Err(NotImplementedError::with_message("Time#- with Integer argument is not implemented").into());
-    //} else if let Ok(other) = other.try_convert_into::<f64>(interp) {
-    //let _ = other;
-    //return Err(NotImplementedError::with_message("Time#- with Float argument is not implemented").into());
-    //} else {
-    //return Err(TypeError::with_message("can't convert into an exact number").into());
-    //};
-    //let difference = time.sub(*other);
-    //interp.try_convert_mut(difference)
+    if let Ok(other) = unsafe { Time::unbox_from_value(&mut other, interp) } {
+        let seconds_delta = time.to_int() - other.to_int();
+        let nanos_delta = i64::from(time.nanoseconds()) - i64::from(other.nanoseconds());
+        let difference = seconds_delta as f64 + (nanos_delta as f64 / NANOS_PER_SECOND as f64);
+        return interp.try_convert_mut(difference);
+    }
+
+    let (sub_seconds, sub_nanos) = seconds_and_nanos_from_numeric(interp, other)?;
+    let result = time_plus_duration(&time, -sub_seconds, -sub_nanos)?;
+    Time::alloc_value(result, interp)
 }
 
 // Coarse math
 
-pub fn succ(interp: &mut Artichoke, time: Value) -> Result<Value, Error> {
-    let _ = interp;
-    let _ = time;
-    Err(NotImplementedError::new().into())
+pub fn succ(interp: &mut Artichoke, mut time: Value) -> Result<Value, Error> {
+    interp.warn(b"warning: Time#succ is obsolete; use time + 1")?;
+    let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
+    let result = time_plus_duration(&time, 1, 0)?;
+    Time::alloc_value(result, interp)
+}
 
-    //interp.warn(b"warning: Time#succ is obsolete; use time + 1")?;
-    //let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
-    //let next = time + 1;
-    //Time::alloc_value(next, interp)
+// Rounds `nanos` (in `0..=999_999_999`) to the nearest multiple of `10^(9 - min(ndigits, 9))`,
+// round-half-up. The result may be exactly `1_000_000_000`, signaling a carry into the next
+// second, which `time_plus_duration` resolves when the rounded delta is added back in.
+fn round_nanos_to_digits(nanos: i64, ndigits: i64) -> i64 {
+    let exponent = 9 - ndigits.clamp(0, 9);
+    let divisor = 10i64.pow(exponent as u32);
+    (nanos + divisor / 2) / divisor * divisor
 }
 
-pub fn round(interp: &mut Artichoke, time: Value, num_digits: Option<Value>) -> Result<Value, Error> {
-    let _ = interp;
-    let _ = time;
-    let _ = num_digits;
-    Err(NotImplementedError::new().into())
+pub fn round(interp: &mut Artichoke, mut time: Value, num_digits: Option<Value>) -> Result<Value, Error> {
+    let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
+    let ndigits = match num_digits {
+        Some(value) => implicitly_convert_to_int(interp, value)?,
+        None => 0,
+    };
+    if ndigits < 0 {
+        return Err(ArgumentError::with_message("ndigits must be a non-negative integer").into());
+    }
+
+    let nanos = i64::from(time.nanoseconds());
+    let rounded = round_nanos_to_digits(nanos, ndigits);
+    let result = time_plus_duration(&time, 0, rounded - nanos)?;
+    Time::alloc_value(result, interp)
 }
 
 // Datetime
@@ -379,16 +471,14 @@ pub fn is_dst(interp: &mut Artichoke, mut time: Value) -> Result<Value, Error> {
     Ok(interp.convert(is_dst))
 }
 
-pub fn timezone(interp: &mut Artichoke, time: Value) -> Result<Value, Error> {
-    let _ = interp;
-    let _ = time;
-    Err(NotImplementedError::new().into())
+pub fn timezone(interp: &mut Artichoke, mut time: Value) -> Result<Value, Error> {
+    let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
+    interp.try_convert_mut(convert::designation(&time))
 }
 
-pub fn utc_offset(interp: &mut Artichoke, time: Value) -> Result<Value, Error> {
-    let _ = interp;
-    let _ = time;
-    Err(NotImplementedError::new().into())
+pub fn utc_offset(interp: &mut Artichoke, mut time: Value) -> Result<Value, Error> {
+    let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
+    Ok(interp.convert(convert::utc_offset_seconds(&time)))
 }
 
 // Timezone mode
@@ -475,10 +565,65 @@ pub fn subsec(interp: &mut Artichoke, time: Value) -> Result<Value, Error> {
 
 // Time format
 
-pub fn strftime(interp: &mut Artichoke, time: Value, format: Value) -> Result<Value, Error> {
-    let _ = interp;
-    let _ = time;
-    let _ = format;
-    // Requires a parser.
-    Err(NotImplementedError::new().into())
+pub fn strftime(interp: &mut Artichoke, mut time: Value, mut format: Value) -> Result<Value, Error> {
+    let time = unsafe { Time::unbox_from_value(&mut time, interp)? };
+    let format = unsafe { implicitly_convert_to_string(interp, &mut format)? };
+    let formatted = format::format(&time, format);
+    interp.try_convert_mut(formatted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{round_nanos_to_digits, time_plus_duration};
+    use crate::extn::core::time::{Offset, Time};
+
+    #[test]
+    fn round_nanos_rounds_half_up() {
+        assert_eq!(0, round_nanos_to_digits(4, 0));
+        assert_eq!(1_000_000_000, round_nanos_to_digits(500_000_000, 0));
+    }
+
+    #[test]
+    fn round_nanos_carries_into_next_second_at_six_digits() {
+        assert_eq!(1_000_000_000, round_nanos_to_digits(999_999_999, 6));
+    }
+
+    #[test]
+    fn round_nanos_is_a_no_op_past_nine_digits() {
+        assert_eq!(123_456_789, round_nanos_to_digits(123_456_789, 9));
+        assert_eq!(123_456_789, round_nanos_to_digits(123_456_789, 15));
+    }
+
+    #[test]
+    fn time_plus_duration_carries_nanos_into_the_next_second() {
+        let time = Time::with_timespec_and_offset(10, 900_000_000, Offset::Utc).unwrap();
+        let result = time_plus_duration(&time, 0, 200_000_000).unwrap();
+        assert_eq!(11, result.to_int());
+        assert_eq!(100_000_000, result.nanoseconds());
+    }
+
+    #[test]
+    fn time_plus_duration_borrows_nanos_from_the_previous_second() {
+        let time = Time::with_timespec_and_offset(10, 200_000_000, Offset::Utc).unwrap();
+        let result = time_plus_duration(&time, 0, -300_000_000).unwrap();
+        assert_eq!(9, result.to_int());
+        assert_eq!(900_000_000, result.nanoseconds());
+    }
+
+    #[test]
+    fn time_plus_duration_preserves_utc_offset() {
+        let time = Time::with_timespec_and_offset(0, 0, Offset::Utc).unwrap();
+        let result = time_plus_duration(&time, 3600, 0).unwrap();
+        assert!(result.is_utc());
+        assert_eq!(Offset::Utc, result.offset());
+    }
+
+    #[test]
+    fn time_plus_duration_preserves_local_fixed_offset() {
+        let offset = Offset::try_fixed(3600).unwrap();
+        let time = Time::with_timespec_and_offset(0, 0, offset).unwrap();
+        let result = time_plus_duration(&time, 3600, 0).unwrap();
+        assert!(!result.is_utc());
+        assert_eq!(offset, result.offset());
+    }
 }
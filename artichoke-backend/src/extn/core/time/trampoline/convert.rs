@@ -0,0 +1,26 @@
+//! `Offset` -> Ruby value conversions, shared by every trampoline entry point that surfaces an
+//! offset as a signed `Fixnum` of seconds or as an abbreviation `String` (`Time#utc_offset`,
+//! `Time#timezone`, and `Time#to_a`'s trailing `zone` element).
+
+use crate::extn::core::time::Time;
+
+/// Maps `time`'s offset to its signed UTC offset, in seconds, as returned by `Time#utc_offset`.
+#[must_use]
+pub fn utc_offset_seconds(time: &Time) -> i32 {
+    if time.is_utc() {
+        0
+    } else {
+        time.offset().utc_offset_seconds(time.to_int())
+    }
+}
+
+/// Maps `time`'s offset to its zone abbreviation (e.g. `"UTC"`, `"EST"`), as returned by
+/// `Time#timezone` and `Time#to_a`'s trailing `zone` element.
+#[must_use]
+pub fn designation(time: &Time) -> String {
+    if time.is_utc() {
+        String::from("UTC")
+    } else {
+        time.offset().designation(time.to_int())
+    }
+}
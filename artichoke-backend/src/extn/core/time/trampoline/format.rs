@@ -0,0 +1,354 @@
+//! `Time#strftime` directive scanner and renderer.
+//!
+//! Modeled on the C/chrono approach: the format string is scanned once into a sequence of
+//! [`Item`]s (literal byte runs and parsed [`Directive`]s), then each item is rendered against a
+//! `Time` in a second pass.
+
+use super::directives::{DAY_NAMES, MONTH_NAMES};
+use crate::extn::core::time::Time;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pad {
+    None,
+    Space,
+    Zero,
+}
+
+/// Upper bound on a directive's parsed width, matching MRI's practical limits. Without this, a
+/// format string like `%18446744073709551615Y` (`u64::MAX`, which fits in a 64-bit `usize`) would
+/// parse successfully and drive `pad_numeric` into allocating a multi-exabyte string.
+const MAX_DIRECTIVE_WIDTH: usize = 256;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Flags {
+    pad: Option<Pad>,
+    upcase: bool,
+    swapcase: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Directive<'a> {
+    flags: Flags,
+    width: Option<usize>,
+    conversion: u8,
+    /// The directive's original bytes (from the `%` through the conversion character), kept so
+    /// an unsupported conversion can be re-emitted verbatim instead of reconstructed from the
+    /// parsed `flags`/`width`, which would lose information (e.g. a `%005Q` width with leading
+    /// zeros, or a duplicate pad flag where only the last one parsed is kept).
+    raw: &'a [u8],
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Item<'a> {
+    Literal(&'a [u8]),
+    Directive(Directive<'a>),
+}
+
+/// Scans a strftime format string into a sequence of literal runs and directives.
+///
+/// Unsupported directives are still scanned into a `Directive` (so their flags/width participate
+/// in parsing correctly), but the renderer re-emits their `raw` bytes verbatim rather than
+/// interpreting them, matching MRI's behavior for unsupported directives.
+fn scan(format: &[u8]) -> Vec<Item<'_>> {
+    let mut items = Vec::new();
+    let mut idx = 0;
+
+    while idx < format.len() {
+        let start = idx;
+        while idx < format.len() && format[idx] != b'%' {
+            idx += 1;
+        }
+        if idx > start {
+            items.push(Item::Literal(&format[start..idx]));
+        }
+        if idx >= format.len() {
+            break;
+        }
+
+        // `idx` is at the `%`; `directive_start` lets us recover the raw bytes if nothing after
+        // the `%` turns out to be parseable as flags/width/conversion.
+        let directive_start = idx;
+        idx += 1;
+
+        let mut flags = Flags::default();
+        loop {
+            match format.get(idx) {
+                Some(b'-') => {
+                    flags.pad = Some(Pad::None);
+                    idx += 1;
+                }
+                Some(b'_') => {
+                    flags.pad = Some(Pad::Space);
+                    idx += 1;
+                }
+                Some(b'0') => {
+                    flags.pad = Some(Pad::Zero);
+                    idx += 1;
+                }
+                Some(b'^') => {
+                    flags.upcase = true;
+                    idx += 1;
+                }
+                Some(b'#') => {
+                    flags.swapcase = true;
+                    idx += 1;
+                }
+                _ => break,
+            }
+        }
+
+        let width_start = idx;
+        while format.get(idx).is_some_and(u8::is_ascii_digit) {
+            idx += 1;
+        }
+        let width = if idx > width_start {
+            std::str::from_utf8(&format[width_start..idx])
+                .ok()
+                .and_then(|s| s.parse::<usize>().ok())
+                .map(|width| width.min(MAX_DIRECTIVE_WIDTH))
+        } else {
+            None
+        };
+
+        match format.get(idx) {
+            Some(&conversion) => {
+                idx += 1;
+                items.push(Item::Directive(Directive {
+                    flags,
+                    width,
+                    conversion,
+                    raw: &format[directive_start..idx],
+                }));
+            }
+            // Trailing `%` with nothing after it: emit it verbatim.
+            None => items.push(Item::Literal(&format[directive_start..idx])),
+        }
+    }
+
+    items
+}
+
+fn pad_numeric(value: i64, width: usize, pad: Pad) -> String {
+    match pad {
+        Pad::None => value.to_string(),
+        Pad::Space => format!("{value:>width$}"),
+        Pad::Zero if value < 0 => {
+            format!("-{:0>width$}", -value, width = width.saturating_sub(1))
+        }
+        Pad::Zero => format!("{value:0>width$}"),
+    }
+}
+
+fn apply_case(mut s: String, flags: Flags) -> String {
+    if flags.upcase {
+        s.make_ascii_uppercase();
+    } else if flags.swapcase {
+        s = s
+            .chars()
+            .map(|c| {
+                if c.is_ascii_uppercase() {
+                    c.to_ascii_lowercase()
+                } else {
+                    c.to_ascii_uppercase()
+                }
+            })
+            .collect();
+    }
+    s
+}
+
+fn render_directive(time: &Time, directive: Directive<'_>, out: &mut Vec<u8>) {
+    let Directive { flags, width, conversion, raw } = directive;
+
+    let rendered = match conversion {
+        b'Y' => pad_numeric(i64::from(time.year()), width.unwrap_or(4), flags.pad.unwrap_or(Pad::Zero)),
+        b'C' => pad_numeric(i64::from(time.year()) / 100, width.unwrap_or(2), flags.pad.unwrap_or(Pad::Zero)),
+        b'y' => pad_numeric(i64::from(time.year()).rem_euclid(100), width.unwrap_or(2), flags.pad.unwrap_or(Pad::Zero)),
+        b'm' => pad_numeric(i64::from(time.month()), width.unwrap_or(2), flags.pad.unwrap_or(Pad::Zero)),
+        b'd' => pad_numeric(i64::from(time.day()), width.unwrap_or(2), flags.pad.unwrap_or(Pad::Zero)),
+        b'e' => pad_numeric(i64::from(time.day()), width.unwrap_or(2), flags.pad.unwrap_or(Pad::Space)),
+        b'H' => pad_numeric(i64::from(time.hour()), width.unwrap_or(2), flags.pad.unwrap_or(Pad::Zero)),
+        b'I' => {
+            let hour12 = match time.hour() % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            pad_numeric(i64::from(hour12), width.unwrap_or(2), flags.pad.unwrap_or(Pad::Zero))
+        }
+        b'M' => pad_numeric(i64::from(time.minute()), width.unwrap_or(2), flags.pad.unwrap_or(Pad::Zero)),
+        b'S' => pad_numeric(i64::from(time.second()), width.unwrap_or(2), flags.pad.unwrap_or(Pad::Zero)),
+        b'L' => {
+            let width = width.unwrap_or(3);
+            let millis = i64::from(time.nanoseconds()) / 1_000_000;
+            pad_numeric(millis, width, flags.pad.unwrap_or(Pad::Zero))
+        }
+        b'N' => {
+            let width = width.unwrap_or(9);
+            let nanos = format!("{:09}", time.nanoseconds());
+            if width <= 9 {
+                nanos[..width].to_string()
+            } else {
+                format!("{nanos:0<width$}")
+            }
+        }
+        b'p' => String::from(if time.hour() < 12 { "AM" } else { "PM" }),
+        b'P' => String::from(if time.hour() < 12 { "am" } else { "pm" }),
+        b'j' => pad_numeric(i64::from(time.day_of_year()), width.unwrap_or(3), flags.pad.unwrap_or(Pad::Zero)),
+        b'a' => DAY_NAMES[usize::from(time.day_of_week())][..3].to_string(),
+        b'A' => DAY_NAMES[usize::from(time.day_of_week())].to_string(),
+        b'b' | b'h' => MONTH_NAMES[usize::from(time.month() - 1)][..3].to_string(),
+        b'B' => MONTH_NAMES[usize::from(time.month() - 1)].to_string(),
+        b'z' => time.offset().to_hhmm(time.to_int()),
+        b'Z' => {
+            if time.is_utc() {
+                String::from("UTC")
+            } else {
+                time.offset().designation(time.to_int())
+            }
+        }
+        b's' => time.to_int().to_string(),
+        b'%' => String::from("%"),
+        b'n' => String::from("\n"),
+        b't' => String::from("\t"),
+        // Unknown directive: emit the original bytes verbatim rather than reconstructing them,
+        // so nothing is lost to the parsed representation (e.g. a zero-padded width, or a
+        // duplicate pad flag where only the last one parsed survives in `flags`).
+        _ => {
+            out.extend_from_slice(raw);
+            return;
+        }
+    };
+
+    out.extend_from_slice(apply_case(rendered, flags).as_bytes());
+}
+
+/// Renders `time` according to the given strftime `format` string, returning the formatted byte
+/// string.
+#[must_use]
+pub fn format(time: &Time, format: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(format.len());
+    for item in scan(format) {
+        match item {
+            Item::Literal(bytes) => out.extend_from_slice(bytes),
+            Item::Directive(directive) => render_directive(time, directive, &mut out),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format;
+    use crate::extn::core::time::{Offset, Time};
+
+    // 2024-03-05T06:07:08 UTC: a Tuesday, day-of-year 65, in a leap year.
+    fn utc_time() -> Time {
+        Time::with_timespec_and_offset(1_709_618_828, 123_456_789, Offset::Utc).unwrap()
+    }
+
+    // 2024-03-05T15:30:45 UTC, for the 12-hour/meridian directives.
+    fn utc_afternoon() -> Time {
+        Time::with_timespec_and_offset(1_709_652_645, 0, Offset::Utc).unwrap()
+    }
+
+    #[test]
+    fn formats_date_directives() {
+        let time = utc_time();
+        assert_eq!(b"2024-03-05", &*format(&time, b"%Y-%m-%d"));
+        assert_eq!(b"24", &*format(&time, b"%y"));
+        assert_eq!(b"20", &*format(&time, b"%C"));
+        assert_eq!(b"065", &*format(&time, b"%j"));
+    }
+
+    #[test]
+    fn formats_time_of_day_directives() {
+        let time = utc_time();
+        assert_eq!(b"06:07:08", &*format(&time, b"%H:%M:%S"));
+        assert_eq!(b"123", &*format(&time, b"%L"));
+        assert_eq!(b"123456789", &*format(&time, b"%N"));
+    }
+
+    #[test]
+    fn formats_12_hour_and_meridian_directives() {
+        let time = utc_afternoon();
+        assert_eq!(b"03", &*format(&time, b"%I"));
+        assert_eq!(b"PM", &*format(&time, b"%p"));
+        assert_eq!(b"pm", &*format(&time, b"%P"));
+    }
+
+    #[test]
+    fn formats_name_directives() {
+        let time = utc_time();
+        assert_eq!(b"Tue", &*format(&time, b"%a"));
+        assert_eq!(b"Tuesday", &*format(&time, b"%A"));
+        assert_eq!(b"Mar", &*format(&time, b"%b"));
+        assert_eq!(b"March", &*format(&time, b"%B"));
+    }
+
+    #[test]
+    fn formats_offset_and_zone_directives() {
+        let time = utc_time();
+        assert_eq!(b"+0000", &*format(&time, b"%z"));
+        assert_eq!(b"UTC", &*format(&time, b"%Z"));
+    }
+
+    #[test]
+    fn formats_epoch_and_literal_directives() {
+        let time = utc_time();
+        assert_eq!(b"1709618828", &*format(&time, b"%s"));
+        assert_eq!(b"%", &*format(&time, b"%%"));
+        assert_eq!(b"\n", &*format(&time, b"%n"));
+        assert_eq!(b"\t", &*format(&time, b"%t"));
+    }
+
+    #[test]
+    fn dash_flag_disables_padding() {
+        assert_eq!(b"6", &*format(&utc_time(), b"%-H"));
+    }
+
+    #[test]
+    fn underscore_flag_pads_with_spaces() {
+        assert_eq!(b" 6", &*format(&utc_time(), b"%_2H"));
+    }
+
+    #[test]
+    fn zero_flag_pads_with_zeros() {
+        assert_eq!(b"006", &*format(&utc_time(), b"%03H"));
+    }
+
+    #[test]
+    fn caret_flag_upcases() {
+        assert_eq!(b"MAR", &*format(&utc_time(), b"%^b"));
+    }
+
+    #[test]
+    fn hash_flag_swaps_case() {
+        assert_eq!(b"mARCH", &*format(&utc_time(), b"%#B"));
+    }
+
+    #[test]
+    fn width_overrides_default_padding() {
+        assert_eq!(b"002024", &*format(&utc_time(), b"%6Y"));
+    }
+
+    #[test]
+    fn unknown_directive_round_trips_verbatim() {
+        assert_eq!(b"%005Q", &*format(&utc_time(), b"%005Q"));
+    }
+
+    #[test]
+    fn unknown_directive_with_duplicate_pad_flags_round_trips_verbatim() {
+        assert_eq!(b"%-_Q", &*format(&utc_time(), b"%-_Q"));
+    }
+
+    #[test]
+    fn trailing_percent_is_emitted_verbatim() {
+        assert_eq!(b"abc%", &*format(&utc_time(), b"abc%"));
+    }
+
+    #[test]
+    fn huge_width_is_clamped_instead_of_allocating_unbounded_output() {
+        let rendered = format(&utc_time(), b"%18446744073709551615Y");
+        assert_eq!(256, rendered.len());
+    }
+}
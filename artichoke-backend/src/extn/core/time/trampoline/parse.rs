@@ -0,0 +1,428 @@
+//! Inverse of [`super::format`]: a `strptime`-style scanner that walks a format string and an
+//! input string in lockstep, accumulating matched fields into a [`Parsed`], plus the fixed
+//! RFC 2822 and RFC 3339 formats built on top of it.
+
+use super::directives::{DAY_NAMES, MONTH_NAMES};
+use crate::extn::core::time::{Offset, Time};
+use crate::extn::prelude::*;
+
+/// Fields accumulated while scanning a `strptime` format/input pair. Absent fields default to
+/// their Unix-epoch value (year 1970, month/day 1, midnight) when finalized via [`Parsed::into_time`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Parsed {
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    hour: Option<u32>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    nanosecond: Option<u32>,
+    offset_seconds: Option<i32>,
+    /// Set when the scanned `%z` field was the literal `-0000`/`-00:00` "unknown local offset"
+    /// text, so `into_time` can route it to [`Offset::unknown`] instead of an ordinary zero
+    /// [`Offset::Fixed`] offset.
+    offset_unknown: bool,
+    pm: Option<bool>,
+}
+
+impl Parsed {
+    fn set_hour12(&mut self, hour12: u32) {
+        self.hour = Some(hour12 % 12);
+    }
+
+    /// Validates the accumulated fields and resolves them to a concrete `Time`.
+    pub fn into_time(mut self) -> Result<Time, Error> {
+        if let Some(true) = self.pm {
+            self.hour = Some(self.hour.unwrap_or(0) + 12);
+        }
+
+        let year = self.year.unwrap_or(1970);
+        let month = self.month.unwrap_or(1);
+        let day = self.day.unwrap_or(1);
+        let hour = self.hour.unwrap_or(0);
+        let minute = self.minute.unwrap_or(0);
+        let second = self.second.unwrap_or(0);
+        let nanosecond = self.nanosecond.unwrap_or(0);
+
+        if !(1..=12).contains(&month) {
+            return Err(ArgumentError::with_message("invalid month").into());
+        }
+        if day < 1 || day > u32::from(days_in_month(i64::from(year), i64::from(month))) {
+            return Err(ArgumentError::with_message("invalid day").into());
+        }
+        if hour > 23 || minute > 59 || second > 60 {
+            return Err(ArgumentError::with_message("invalid time of day").into());
+        }
+
+        let offset = match (self.offset_seconds, self.offset_unknown) {
+            (Some(_), true) => Offset::unknown(),
+            (Some(seconds), false) => {
+                Offset::try_fixed(seconds).map_err(|_| ArgumentError::with_message("utc_offset out of range"))?
+            }
+            (None, _) => Offset::local(),
+        };
+
+        let local_seconds = days_from_civil(i64::from(year), month, day) * 86_400
+            + i64::from(hour) * 3600
+            + i64::from(minute) * 60
+            + i64::from(second);
+        let unix_seconds = local_seconds - i64::from(offset_seconds_hint(&offset, local_seconds));
+
+        Time::with_timespec_and_offset(unix_seconds, nanosecond, offset)
+            .map_err(|_| ArgumentError::with_message("Time too large").into())
+    }
+}
+
+/// Approximates the offset in seconds for a freshly-constructed `Offset` by projecting it at the
+/// parsed wall-clock seconds read as if they were a Unix timestamp. This is exact for
+/// [`Offset::Utc`], [`Offset::Unknown`], and [`Offset::Fixed`]; for a [`Offset::Tz`] it can be off
+/// by the DST delta near a transition, which is an inherent chicken-and-egg problem (the offset is
+/// needed to compute the timestamp, and vice versa).
+fn offset_seconds_hint(offset: &Offset, local_seconds: i64) -> i32 {
+    offset.utc_offset_seconds(local_seconds)
+}
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// The number of days in `month` of `year` (1-indexed month), accounting for leap years.
+pub(super) fn days_in_month(year: i64, month: i64) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => unreachable!("month is normalized to 1..=12"),
+    }
+}
+
+/// Days since the Unix epoch (1970-01-01) for a proleptic Gregorian civil date, using Howard
+/// Hinnant's `days_from_civil` algorithm.
+pub(super) fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (i64::from(m) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn consume_digits(input: &[u8], idx: &mut usize, max: usize) -> Option<u32> {
+    let start = *idx;
+    while *idx < input.len() && *idx - start < max && input[*idx].is_ascii_digit() {
+        *idx += 1;
+    }
+    if *idx == start {
+        return None;
+    }
+    std::str::from_utf8(&input[start..*idx]).ok()?.parse().ok()
+}
+
+/// Scans a `±HH:MM`/`±HHMM`/`Z` offset field, returning its signed seconds and whether it was the
+/// literal `-0000`/`-00:00` "unknown local offset" text (see [`Offset::Unknown`]), which must not
+/// collapse into an ordinary zero [`Offset::Fixed`] offset.
+fn consume_offset(input: &[u8], idx: &mut usize) -> Option<(i32, bool)> {
+    if input.get(*idx) == Some(&b'Z') {
+        *idx += 1;
+        return Some((0, false));
+    }
+    let sign = match input.get(*idx)? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    *idx += 1;
+    let hours = consume_digits(input, idx, 2)?;
+    if input.get(*idx) == Some(&b':') {
+        *idx += 1;
+    }
+    let minutes = consume_digits(input, idx, 2)?;
+    let seconds = sign * (i32::try_from(hours).ok()? * 3600 + i32::try_from(minutes).ok()? * 60);
+    let is_unknown = sign < 0 && hours == 0 && minutes == 0;
+    Some((seconds, is_unknown))
+}
+
+/// Matches `input[*idx..]` case-insensitively against a static name table, advancing `*idx` past
+/// the match. Longer names are tried first so `"June"` isn't shadowed by a `"Jun"`-only table.
+fn match_name(input: &[u8], idx: &mut usize, names: &[&str]) -> Option<usize> {
+    let remaining = &input[*idx..];
+    let mut candidates: Vec<(usize, &str)> = names.iter().copied().enumerate().collect();
+    candidates.sort_by_key(|(_, name)| std::cmp::Reverse(name.len()));
+
+    for (position, name) in candidates {
+        let name_bytes = name.as_bytes();
+        if remaining.len() >= name_bytes.len() && remaining[..name_bytes.len()].eq_ignore_ascii_case(name_bytes) {
+            *idx += name_bytes.len();
+            return Some(position);
+        }
+        let abbrev = &name_bytes[..name_bytes.len().min(3)];
+        if remaining.len() >= abbrev.len() && remaining[..abbrev.len()].eq_ignore_ascii_case(abbrev) {
+            *idx += abbrev.len();
+            return Some(position);
+        }
+    }
+    None
+}
+
+fn consume_directive(conversion: u8, input: &[u8], idx: &mut usize, parsed: &mut Parsed) -> Result<(), Error> {
+    let invalid = || Error::from(ArgumentError::with_message("invalid strptime input"));
+
+    match conversion {
+        b'Y' => parsed.year = Some(i32::try_from(consume_digits(input, idx, 4).ok_or_else(invalid)?).unwrap()),
+        b'y' => {
+            let year = consume_digits(input, idx, 2).ok_or_else(invalid)?;
+            parsed.year = Some(if year < 69 { 2000 + year as i32 } else { 1900 + year as i32 });
+        }
+        b'm' => parsed.month = Some(consume_digits(input, idx, 2).ok_or_else(invalid)?),
+        b'd' | b'e' => parsed.day = Some(consume_digits(input, idx, 2).ok_or_else(invalid)?),
+        b'H' => parsed.hour = Some(consume_digits(input, idx, 2).ok_or_else(invalid)?),
+        b'I' => parsed.set_hour12(consume_digits(input, idx, 2).ok_or_else(invalid)?),
+        b'M' => parsed.minute = Some(consume_digits(input, idx, 2).ok_or_else(invalid)?),
+        b'S' => parsed.second = Some(consume_digits(input, idx, 2).ok_or_else(invalid)?),
+        b'z' => {
+            let (offset_seconds, is_unknown) = consume_offset(input, idx).ok_or_else(invalid)?;
+            parsed.offset_seconds = Some(offset_seconds);
+            parsed.offset_unknown = is_unknown;
+        }
+        b'b' | b'B' | b'h' => {
+            let month = match_name(input, idx, &MONTH_NAMES).ok_or_else(invalid)?;
+            parsed.month = Some(u32::try_from(month).unwrap() + 1);
+        }
+        b'a' | b'A' => {
+            // Weekday is redundant with the date and is not stored; MRI only uses it to validate.
+            match_name(input, idx, &DAY_NAMES).ok_or_else(invalid)?;
+        }
+        b'p' | b'P' => {
+            let remaining = &input[*idx..];
+            if remaining.len() >= 2 && remaining[..2].eq_ignore_ascii_case(b"PM") {
+                parsed.pm = Some(true);
+                *idx += 2;
+            } else if remaining.len() >= 2 && remaining[..2].eq_ignore_ascii_case(b"AM") {
+                parsed.pm = Some(false);
+                *idx += 2;
+            } else {
+                return Err(invalid());
+            }
+        }
+        b'%' => {
+            if input.get(*idx) != Some(&b'%') {
+                return Err(invalid());
+            }
+            *idx += 1;
+        }
+        _ => return Err(ArgumentError::with_message("unsupported strptime directive").into()),
+    }
+
+    Ok(())
+}
+
+/// Scans `input` against `format`, accumulating matched fields. Literal bytes in `format` must
+/// match exactly, except that a run of whitespace in `format` matches any run of whitespace (of
+/// any length) in `input`. Trailing unparsed input is rejected.
+pub fn strptime(input: &[u8], format: &[u8]) -> Result<Parsed, Error> {
+    let mut parsed = Parsed::default();
+    let mut in_idx = 0;
+    let mut fmt_idx = 0;
+
+    while fmt_idx < format.len() {
+        match format[fmt_idx] {
+            b'%' => {
+                fmt_idx += 1;
+                let conversion = *format
+                    .get(fmt_idx)
+                    .ok_or_else(|| ArgumentError::with_message("invalid format string"))?;
+                fmt_idx += 1;
+                consume_directive(conversion, input, &mut in_idx, &mut parsed)?;
+            }
+            byte if byte.is_ascii_whitespace() => {
+                while fmt_idx < format.len() && format[fmt_idx].is_ascii_whitespace() {
+                    fmt_idx += 1;
+                }
+                while in_idx < input.len() && input[in_idx].is_ascii_whitespace() {
+                    in_idx += 1;
+                }
+            }
+            byte => {
+                if input.get(in_idx) != Some(&byte) {
+                    return Err(ArgumentError::with_message("invalid strptime format").into());
+                }
+                in_idx += 1;
+                fmt_idx += 1;
+            }
+        }
+    }
+
+    if in_idx != input.len() {
+        return Err(ArgumentError::with_message("trailing unparsed input").into());
+    }
+
+    Ok(parsed)
+}
+
+/// Parses `Day, DD Mon YYYY HH:MM:SS ±HHMM`, the RFC 2822 date-time format.
+pub fn parse_rfc2822(input: &[u8]) -> Result<Parsed, Error> {
+    strptime(input, b"%a, %d %b %Y %H:%M:%S %z")
+}
+
+/// Parses `YYYY-MM-DDTHH:MM:SS[.fraction]±HH:MM`, the RFC 3339 date-time format.
+///
+/// The optional fractional-seconds component isn't expressible in the generic directive table
+/// (it sits between `%S` and the offset with no directive of its own), so it's located and
+/// stripped before delegating the surrounding fixed-format pieces to [`strptime`].
+pub fn parse_rfc3339(input: &[u8]) -> Result<Parsed, Error> {
+    let Some(dot_idx) = input.iter().position(|&b| b == b'.') else {
+        return strptime(input, b"%Y-%m-%dT%H:%M:%S%z");
+    };
+
+    let (head, tail) = input.split_at(dot_idx);
+    let tail = &tail[1..];
+    let digits_end = tail.iter().position(|b| !b.is_ascii_digit()).unwrap_or(tail.len());
+    let (fraction, rest) = tail.split_at(digits_end);
+
+    let mut parsed = strptime(head, b"%Y-%m-%dT%H:%M:%S")?;
+
+    let mut digits = std::str::from_utf8(fraction)
+        .map_err(|_| ArgumentError::with_message("invalid fractional seconds"))?
+        .to_string();
+    digits.truncate(9);
+    while digits.len() < 9 {
+        digits.push('0');
+    }
+    parsed.nanosecond = Some(
+        digits
+            .parse()
+            .map_err(|_| ArgumentError::with_message("invalid fractional seconds"))?,
+    );
+
+    let offset = strptime(rest, b"%z")?;
+    parsed.offset_seconds = offset.offset_seconds;
+    parsed.offset_unknown = offset.offset_unknown;
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{days_from_civil, days_in_month, parse_rfc2822, parse_rfc3339, strptime};
+    use crate::extn::core::time::Offset;
+
+    #[test]
+    fn days_from_civil_matches_known_epoch_offsets() {
+        assert_eq!(0, days_from_civil(1970, 1, 1));
+        assert_eq!(-1, days_from_civil(1969, 12, 31));
+        assert_eq!(19_787, days_from_civil(2024, 3, 5));
+    }
+
+    #[test]
+    fn strptime_parses_numeric_date_and_time() {
+        let parsed = strptime(b"2024-03-05 06:07:08", b"%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(Some(2024), parsed.year);
+        assert_eq!(Some(3), parsed.month);
+        assert_eq!(Some(5), parsed.day);
+        assert_eq!(Some(6), parsed.hour);
+        assert_eq!(Some(7), parsed.minute);
+        assert_eq!(Some(8), parsed.second);
+    }
+
+    #[test]
+    fn strptime_parses_month_name_and_twelve_hour_clock() {
+        let parsed = strptime(b"05 Mar 2024 03:07:08 PM", b"%d %b %Y %I:%M:%S %p").unwrap();
+        assert_eq!(Some(3), parsed.month);
+        assert_eq!(Some(true), parsed.pm);
+        assert_eq!(Some(3), parsed.hour);
+    }
+
+    #[test]
+    fn strptime_parses_two_digit_year_pivot() {
+        let recent = strptime(b"24", b"%y").unwrap();
+        assert_eq!(Some(2024), recent.year);
+
+        let old = strptime(b"season 99", b"season %y").unwrap();
+        assert_eq!(Some(1999), old.year);
+    }
+
+    #[test]
+    fn strptime_literal_whitespace_matches_any_run_of_whitespace() {
+        let parsed = strptime(b"2024-03-05   06:07:08", b"%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(Some(6), parsed.hour);
+    }
+
+    #[test]
+    fn strptime_rejects_trailing_unparsed_input() {
+        assert!(strptime(b"2024-03-05 extra", b"%Y-%m-%d").is_err());
+    }
+
+    #[test]
+    fn strptime_rejects_malformed_field() {
+        assert!(strptime(b"2024-XX-05", b"%Y-%m-%d").is_err());
+    }
+
+    #[test]
+    fn strptime_into_time_resolves_to_unix_seconds() {
+        let time = strptime(b"2024-03-05 06:07:08", b"%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .into_time()
+            .unwrap();
+        assert_eq!(1_709_618_828, time.to_int());
+    }
+
+    #[test]
+    fn parses_rfc2822() {
+        let time = parse_rfc2822(b"Tue, 05 Mar 2024 06:07:08 +0000").unwrap().into_time().unwrap();
+        assert_eq!(1_709_618_828, time.to_int());
+    }
+
+    #[test]
+    fn parses_rfc3339() {
+        let time = parse_rfc3339(b"2024-03-05T06:07:08+00:00").unwrap().into_time().unwrap();
+        assert_eq!(1_709_618_828, time.to_int());
+    }
+
+    #[test]
+    fn parses_rfc3339_with_fractional_seconds() {
+        let parsed = parse_rfc3339(b"2024-03-05T06:07:08.123456789+00:00").unwrap();
+        assert_eq!(Some(123_456_789), parsed.nanosecond);
+        let time = parsed.into_time().unwrap();
+        assert_eq!(1_709_618_828, time.to_int());
+        assert_eq!(123_456_789, time.nanoseconds());
+    }
+
+    #[test]
+    fn parses_rfc3339_with_short_fraction_is_zero_padded() {
+        let parsed = parse_rfc3339(b"2024-03-05T06:07:08.5+00:00").unwrap();
+        assert_eq!(Some(500_000_000), parsed.nanosecond);
+    }
+
+    #[test]
+    fn parses_rfc2822_unknown_local_offset() {
+        let time = parse_rfc2822(b"Tue, 05 Mar 2024 06:07:08 -0000").unwrap().into_time().unwrap();
+        assert_eq!(Offset::unknown(), time.offset());
+    }
+
+    #[test]
+    fn parses_rfc3339_unknown_local_offset() {
+        let time = parse_rfc3339(b"2024-03-05T06:07:08-00:00").unwrap().into_time().unwrap();
+        assert_eq!(Offset::unknown(), time.offset());
+    }
+
+    #[test]
+    fn parses_rfc2822_ordinary_zero_offset_is_not_unknown() {
+        let time = parse_rfc2822(b"Tue, 05 Mar 2024 06:07:08 +0000").unwrap().into_time().unwrap();
+        assert_ne!(Offset::unknown(), time.offset());
+    }
+
+    #[test]
+    fn strptime_rejects_day_out_of_range_for_month() {
+        assert!(strptime(b"2024-02-30", b"%Y-%m-%d").unwrap().into_time().is_err());
+        assert!(strptime(b"2024-04-31", b"%Y-%m-%d").unwrap().into_time().is_err());
+    }
+
+    #[test]
+    fn days_in_month_accounts_for_leap_years() {
+        assert_eq!(29, days_in_month(2024, 2));
+        assert_eq!(28, days_in_month(2023, 2));
+        assert_eq!(31, days_in_month(2024, 1));
+    }
+}
@@ -0,0 +1,291 @@
+//! Natural-language relative time expressions (`"tomorrow"`, `"2 days ago"`, `"next monday"`,
+//! `"1 week 2 days"`, ...), for `Time.parse_relative`.
+//!
+//! An expression is scanned into an intermediate [`Expr`] (a [`Span`] of calendar/duration fields
+//! plus an optional [`Anchor`]), then [`evaluate`] resolves it against a base `Time`.
+
+use super::directives::DAY_NAMES;
+use crate::extn::core::time::Time;
+use crate::extn::prelude::*;
+
+/// A calendar-aware offset: years/months are resolved against the calendar (clamping
+/// day-of-month), weeks/days/hours/minutes/seconds are added as exact durations.
+#[derive(Debug, Default, Clone, Copy)]
+struct Span {
+    years: i64,
+    months: i64,
+    weeks: i64,
+    days: i64,
+    hours: i64,
+    minutes: i64,
+    seconds: i64,
+}
+
+/// A fixed point relative to the base time, resolved after the `Span` is applied.
+#[derive(Debug, Clone, Copy)]
+enum Anchor {
+    None,
+    Today,
+    Tomorrow,
+    Yesterday,
+    Next(u8),
+    Last(u8),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Expr {
+    anchor: Anchor,
+    span: Span,
+}
+
+fn weekday_from_name(word: &str) -> Option<u8> {
+    DAY_NAMES
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(word))
+        .map(|position| position as u8)
+}
+
+fn apply_unit(span: &mut Span, word: &str, amount: i64) -> Result<(), Error> {
+    let singular = word.strip_suffix('s').unwrap_or(word);
+    match singular {
+        "year" => span.years += amount,
+        "month" => span.months += amount,
+        "week" => span.weeks += amount,
+        "day" => span.days += amount,
+        "hour" => span.hours += amount,
+        "minute" | "min" => span.minutes += amount,
+        "second" | "sec" => span.seconds += amount,
+        _ => return Err(ArgumentError::with_message("unrecognized relative time unit").into()),
+    }
+    Ok(())
+}
+
+/// Parses a chain of `<number> <unit>` pairs (e.g. `"1 week 2 days"`), scaling each unit's amount
+/// by `sign`.
+fn parse_span(tokens: &[&str], sign: i64) -> Result<Span, Error> {
+    if tokens.is_empty() {
+        return Err(ArgumentError::with_message("empty relative time expression").into());
+    }
+
+    let mut span = Span::default();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        let amount: i64 = tokens[idx]
+            .parse()
+            .map_err(|_| ArgumentError::with_message("expected a number in relative time expression"))?;
+        idx += 1;
+        let unit = tokens
+            .get(idx)
+            .ok_or_else(|| ArgumentError::with_message("expected a time unit in relative time expression"))?;
+        idx += 1;
+        apply_unit(&mut span, unit, sign * amount)?;
+    }
+    Ok(span)
+}
+
+/// Parses the word following a `next`/`last` keyword: either a weekday name (producing an
+/// `Anchor`) or a single unit name (producing a one-unit `Span`, e.g. `"next week"`).
+fn parse_directional(rest: &[&str], sign: i64) -> Result<Expr, Error> {
+    let [word] = rest else {
+        return Err(ArgumentError::with_message("invalid relative time expression").into());
+    };
+
+    if let Some(weekday) = weekday_from_name(word) {
+        let anchor = if sign > 0 { Anchor::Next(weekday) } else { Anchor::Last(weekday) };
+        return Ok(Expr { anchor, span: Span::default() });
+    }
+
+    let mut span = Span::default();
+    apply_unit(&mut span, word, sign)?;
+    Ok(Expr { anchor: Anchor::None, span })
+}
+
+fn parse(tokens: &[&str]) -> Result<Expr, Error> {
+    match tokens {
+        [] => Err(ArgumentError::with_message("empty relative time expression").into()),
+        ["now"] => Ok(Expr { anchor: Anchor::None, span: Span::default() }),
+        ["today"] => Ok(Expr { anchor: Anchor::Today, span: Span::default() }),
+        ["tomorrow"] => Ok(Expr { anchor: Anchor::Tomorrow, span: Span::default() }),
+        ["yesterday"] => Ok(Expr { anchor: Anchor::Yesterday, span: Span::default() }),
+        ["next", rest @ ..] => parse_directional(rest, 1),
+        ["last", rest @ ..] => parse_directional(rest, -1),
+        ["in", rest @ ..] => Ok(Expr {
+            anchor: Anchor::None,
+            span: parse_span(rest, 1)?,
+        }),
+        [.., &"ago"] => {
+            let chain = &tokens[..tokens.len() - 1];
+            Ok(Expr {
+                anchor: Anchor::None,
+                span: parse_span(chain, -1)?,
+            })
+        }
+        _ => Ok(Expr {
+            anchor: Anchor::None,
+            span: parse_span(tokens, 1)?,
+        }),
+    }
+}
+
+/// Adds `delta_months` to `(year, month)`, wrapping the month and carrying into the year.
+fn add_months(year: i64, month: i64, delta_months: i64) -> (i64, i64) {
+    let total = year * 12 + (month - 1) + delta_months;
+    (total.div_euclid(12), total.rem_euclid(12) + 1)
+}
+
+/// Rebuilds a `Time` from broken-down calendar fields and a known offset-in-seconds, reusing
+/// [`super::parse::days_from_civil`] for the calendar-to-Unix-seconds conversion.
+fn construct_time(
+    year: i64,
+    month: i64,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+    offset: crate::extn::core::time::Offset,
+    offset_seconds: i32,
+) -> Result<Time, Error> {
+    let unix_seconds = super::parse::days_from_civil(year, month as u32, u32::from(day)) * 86_400
+        + i64::from(hour) * 3600
+        + i64::from(minute) * 60
+        + i64::from(second)
+        - i64::from(offset_seconds);
+
+    Time::with_timespec_and_offset(unix_seconds, nanosecond, offset)
+        .map_err(|_| ArgumentError::with_message("Time too large").into())
+}
+
+fn evaluate(base: &Time, expr: &Expr) -> Result<Time, Error> {
+    let offset_seconds = if base.is_utc() { 0 } else { base.offset().utc_offset_seconds(base.to_int()) };
+
+    let total_months = expr.span.years * 12 + expr.span.months;
+    let (year, month) = add_months(i64::from(base.year()), i64::from(base.month()), total_months);
+    let day = base.day().min(super::parse::days_in_month(year, month));
+
+    let calendar_adjusted = construct_time(
+        year,
+        month,
+        day,
+        base.hour(),
+        base.minute(),
+        base.second(),
+        base.nanoseconds(),
+        base.offset(),
+        offset_seconds,
+    )?;
+
+    let mut extra_days = expr.span.weeks * 7 + expr.span.days;
+    extra_days += match expr.anchor {
+        Anchor::Tomorrow => 1,
+        Anchor::Yesterday => -1,
+        _ => 0,
+    };
+    let extra_seconds =
+        extra_days * 86_400 + expr.span.hours * 3600 + expr.span.minutes * 60 + expr.span.seconds;
+
+    let mut result = super::time_plus_duration(&calendar_adjusted, extra_seconds, 0)?;
+
+    match expr.anchor {
+        Anchor::Next(weekday) => loop {
+            result = super::time_plus_duration(&result, 86_400, 0)?;
+            if result.day_of_week() == weekday {
+                break;
+            }
+        },
+        Anchor::Last(weekday) => loop {
+            result = super::time_plus_duration(&result, -86_400, 0)?;
+            if result.day_of_week() == weekday {
+                break;
+            }
+        },
+        Anchor::None | Anchor::Today | Anchor::Tomorrow | Anchor::Yesterday => {}
+    }
+
+    Ok(result)
+}
+
+/// Parses `input` as a relative time expression and resolves it against `base`, returning a new
+/// `Time` in `base`'s offset.
+pub fn parse_relative(input: &[u8], base: &Time) -> Result<Time, Error> {
+    let text =
+        std::str::from_utf8(input).map_err(|_| ArgumentError::with_message("invalid relative time expression"))?;
+
+    let lowered: Vec<String> = text.split_whitespace().map(str::to_ascii_lowercase).collect();
+    if lowered.is_empty() {
+        return Err(ArgumentError::with_message("empty relative time expression").into());
+    }
+    let tokens: Vec<&str> = lowered.iter().map(String::as_str).collect();
+
+    let expr = parse(&tokens)?;
+    evaluate(base, &expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_relative;
+    use crate::extn::core::time::{Offset, Time};
+
+    // 2024-03-05T06:07:08 UTC, a Tuesday.
+    fn base() -> Time {
+        Time::with_timespec_and_offset(1_709_618_828, 0, Offset::Utc).unwrap()
+    }
+
+    #[test]
+    fn tomorrow_and_yesterday() {
+        assert_eq!(1_709_705_228, parse_relative(b"tomorrow", &base()).unwrap().to_int());
+        assert_eq!(1_709_532_428, parse_relative(b"yesterday", &base()).unwrap().to_int());
+    }
+
+    #[test]
+    fn n_units_ago() {
+        assert_eq!(1_709_446_028, parse_relative(b"2 days ago", &base()).unwrap().to_int());
+    }
+
+    #[test]
+    fn in_n_units() {
+        assert_eq!(1_709_629_628, parse_relative(b"in 3 hours", &base()).unwrap().to_int());
+    }
+
+    #[test]
+    fn chained_units() {
+        assert_eq!(1_710_396_428, parse_relative(b"1 week 2 days", &base()).unwrap().to_int());
+    }
+
+    #[test]
+    fn month_end_clamps_to_the_shorter_month() {
+        // 2024-01-31 + 1 month clamps to 2024-02-29 (2024 is a leap year), not March 2nd.
+        let jan31 = Time::with_timespec_and_offset(1_706_659_200, 0, Offset::Utc).unwrap();
+        let result = parse_relative(b"in 1 month", &jan31).unwrap();
+        assert_eq!(1_709_164_800, result.to_int());
+    }
+
+    #[test]
+    fn next_weekday_always_moves_strictly_forward() {
+        // 2024-03-04 12:00:00 UTC is itself a Monday; "next monday" must land a full week later,
+        // not on the base day.
+        let monday = Time::with_timespec_and_offset(1_709_553_600, 0, Offset::Utc).unwrap();
+        let result = parse_relative(b"next monday", &monday).unwrap();
+        assert_eq!(1_710_158_400, result.to_int());
+    }
+
+    #[test]
+    fn is_case_insensitive_and_tolerates_extra_whitespace() {
+        assert_eq!(
+            parse_relative(b"tomorrow", &base()).unwrap().to_int(),
+            parse_relative(b"  ToMoRRow  ", &base()).unwrap().to_int()
+        );
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(parse_relative(b"", &base()).is_err());
+        assert!(parse_relative(b"   ", &base()).is_err());
+    }
+
+    #[test]
+    fn rejects_garbage_input() {
+        assert!(parse_relative(b"flibbertigibbet", &base()).is_err());
+        assert!(parse_relative(b"3 bananas ago", &base()).is_err());
+    }
+}
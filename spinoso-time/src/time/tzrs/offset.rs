@@ -1,7 +1,6 @@
 use core::fmt;
 use std::error;
 
-use regex::Regex;
 use tz::timezone::{LocalTimeType, TimeZoneRef};
 use tzdb::local_tz;
 use tzdb::time_zone::etc::GMT;
@@ -20,6 +19,10 @@ impl error::Error for TzStringError {}
 const SECONDS_IN_MINUTE: i32 = 60;
 const SECONDS_IN_HOUR: i32 = SECONDS_IN_MINUTE * 60;
 
+/// The largest magnitude a fixed offset may have: `23:59:59`, matching the range the `time` crate
+/// enforces for `UtcOffset`.
+const MAX_FIXED_OFFSET_SECONDS: u32 = 23 * 3600 + 59 * 60 + 59;
+
 /// tzdb provides [`local_tz`] to get the local system timezone. If this ever fails, we can
 /// assume `GMT`. `GMT` is used instead of `UTC` since it has a [`time_zone_designation`] - which
 /// if it is an empty string, then it is considered to be a UTC time.
@@ -52,12 +55,32 @@ fn offset_hhmm_from_seconds(seconds: i32) -> String {
     format!("{}{:0>2}{:0>2}", flag, offset_hours, offset_minutes)
 }
 
+/// Splits a signed offset in seconds into its `(sign, hours, minutes, seconds)` components.
+#[inline]
+#[must_use]
+fn offset_hms_from_seconds(seconds: i32) -> (char, i32, i32, i32) {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let total = seconds.abs();
+
+    let hours = total / SECONDS_IN_HOUR;
+    let minutes = (total % SECONDS_IN_HOUR) / SECONDS_IN_MINUTE;
+    let secs = total % SECONDS_IN_MINUTE;
+
+    (sign, hours, minutes, secs)
+}
+
 /// Represents the number of seconds offset from UTC
 #[allow(variant_size_differences)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Offset {
     /// UTC offset, zero offset, Zulu time
     Utc,
+    /// The RFC 2822 `-00:00` offset: the time is UTC, but the local offset that produced it is
+    /// unknown.
+    ///
+    /// Note: this is distinct from both [`Offset::Utc`] and a zero [`Offset::Fixed`] offset, and
+    /// must round-trip back to `-0000`/`-00:00` rather than `+0000`/`+00:00`.
+    Unknown,
     /// Fixed offset from UTC
     ///
     /// Note: A fixed offset of 0 is different from UTC time
@@ -74,6 +97,14 @@ impl<'a> Offset {
         Self::Utc
     }
 
+    /// Generate the "unknown local offset" marker used by RFC 2822's `-0000`/`-00:00`: the time
+    /// is UTC, but the originating local offset is unknown.
+    #[inline]
+    #[must_use]
+    pub fn unknown() -> Self {
+        Self::Unknown
+    }
+
     /// Generate an offset based on the detected local time zone of the system
     ///
     /// Detection is done by [`tzdb::local_tz`], and if it fails will return a GMT timezone
@@ -86,6 +117,10 @@ impl<'a> Offset {
     }
 
     /// Generate an offset with a number of seconds from UTC.
+    ///
+    /// This constructor does not validate that `offset` is in range. Out-of-range offsets (magnitude
+    /// greater than 23:59:59) produce a malformed designation, e.g. `Offset::fixed(360_000)` reports
+    /// as `"+10000"`. Prefer [`Offset::try_fixed`] when `offset` is not already known to be in range.
     #[inline]
     #[must_use]
     pub fn fixed(offset: i32) -> Self {
@@ -95,6 +130,19 @@ impl<'a> Offset {
         Self::Fixed([local_time_type])
     }
 
+    /// Generate an offset with a number of seconds from UTC, rejecting offsets outside of
+    /// `±23:59:59` (`±86399` seconds).
+    ///
+    /// This mirrors MRI `Time`, which raises `ArgumentError: utc_offset out of range` for offsets
+    /// beyond this range.
+    #[inline]
+    pub fn try_fixed(offset: i32) -> Result<Self, TzStringError> {
+        if offset.unsigned_abs() > MAX_FIXED_OFFSET_SECONDS {
+            return Err(TzStringError(format!("utc_offset out of range: {offset}")));
+        }
+        Ok(Self::fixed(offset))
+    }
+
     /// Generate an offset based on a provided [`tz::timezone::TimeZoneRef`]
     ///
     /// This can be combined with [`tzdb`] to generate offsets based on predefined iana time zones
@@ -118,7 +166,7 @@ impl<'a> Offset {
     #[must_use]
     pub fn time_zone_ref(&'a self) -> TimeZoneRef<'a> {
         match self {
-            Self::Utc => TimeZoneRef::utc(),
+            Self::Utc | Self::Unknown => TimeZoneRef::utc(),
             Self::Fixed(local_time_types) => match TimeZoneRef::new(&[], local_time_types, &[], &None) {
                 Ok(tz) => tz,
                 Err(_) => GMT,
@@ -127,6 +175,166 @@ impl<'a> Offset {
             Self::Tz(zone) => *zone,
         }
     }
+
+    /// Resolves this `Offset` to the `(ut_offset, designation)` in effect at the given unix
+    /// timestamp.
+    ///
+    /// For [`Offset::Tz`], the offset and designation vary across DST transitions, so the
+    /// resolution is projected against `unix_seconds` via [`TimeZoneRef::find_local_time_type`].
+    ///
+    /// [`Offset::Unknown`] always resolves to a zero offset; callers that need to distinguish it
+    /// from [`Offset::Utc`] and a zero [`Offset::Fixed`] offset should check the variant directly,
+    /// as the formatting methods below do.
+    #[inline]
+    #[must_use]
+    fn local_time_type_at(&'a self, unix_seconds: i64) -> (i32, &'a str) {
+        match self {
+            Self::Utc | Self::Unknown => (0, "UTC"),
+            Self::Fixed(local_time_types) => {
+                let ltt = &local_time_types[0];
+                (ltt.ut_offset(), ltt.time_zone_designation())
+            }
+            Self::Tz(zone) => match zone.find_local_time_type(unix_seconds) {
+                Ok(ltt) => (ltt.ut_offset(), ltt.time_zone_designation()),
+                Err(_) => (0, "UTC"),
+            },
+        }
+    }
+
+    /// Returns the raw UTC offset in seconds in effect at `unix_seconds`.
+    ///
+    /// Unlike the formatting methods below, [`Offset::Unknown`] reports `0` here: its "unknown
+    /// local offset" semantics only affect how it is rendered, not its underlying numeric offset.
+    #[inline]
+    #[must_use]
+    pub fn utc_offset_seconds(&'a self, unix_seconds: i64) -> i32 {
+        self.local_time_type_at(unix_seconds).0
+    }
+
+    /// Formats the offset in effect at `unix_seconds` as `±HHMM` (e.g. `+0530`), matching
+    /// strftime's `%z`.
+    ///
+    /// [`Offset::Unknown`] formats as `-0000`, per RFC 2822.
+    #[inline]
+    #[must_use]
+    pub fn to_hhmm(&'a self, unix_seconds: i64) -> String {
+        if matches!(self, Self::Unknown) {
+            return String::from("-0000");
+        }
+        let (ut_offset, _) = self.local_time_type_at(unix_seconds);
+        offset_hhmm_from_seconds(ut_offset)
+    }
+
+    /// Formats the offset in effect at `unix_seconds` as `±HH:MM` (e.g. `+05:30`), matching
+    /// strftime's `%:z`.
+    ///
+    /// [`Offset::Unknown`] formats as `-00:00`, per RFC 2822.
+    #[inline]
+    #[must_use]
+    pub fn to_hh_colon_mm(&'a self, unix_seconds: i64) -> String {
+        if matches!(self, Self::Unknown) {
+            return String::from("-00:00");
+        }
+        let (sign, hours, minutes, _) = offset_hms_from_seconds(self.local_time_type_at(unix_seconds).0);
+        format!("{sign}{hours:02}:{minutes:02}")
+    }
+
+    /// Formats the offset in effect at `unix_seconds` as `±HH:MM:SS` (e.g. `+05:30:15`), matching
+    /// strftime's `%::z`.
+    ///
+    /// [`Offset::Unknown`] formats as `-00:00:00`, per RFC 2822.
+    #[inline]
+    #[must_use]
+    pub fn to_hh_mm_ss(&'a self, unix_seconds: i64) -> String {
+        if matches!(self, Self::Unknown) {
+            return String::from("-00:00:00");
+        }
+        let (sign, hours, minutes, seconds) = offset_hms_from_seconds(self.local_time_type_at(unix_seconds).0);
+        format!("{sign}{hours:02}:{minutes:02}:{seconds:02}")
+    }
+
+    /// Returns the zone designation in effect at `unix_seconds` (e.g. `UTC`, `GMT`, or an IANA
+    /// zone abbreviation), matching strftime's `%Z`.
+    ///
+    /// Falls back to the `±HHMM` form when the underlying `LocalTimeType` has no designation.
+    /// [`Offset::Unknown`] has no zone abbreviation, so it falls back to `-0000`.
+    #[inline]
+    #[must_use]
+    pub fn designation(&'a self, unix_seconds: i64) -> String {
+        if matches!(self, Self::Unknown) {
+            return String::from("-0000");
+        }
+        let (ut_offset, designation) = self.local_time_type_at(unix_seconds);
+        if designation.is_empty() {
+            offset_hhmm_from_seconds(ut_offset)
+        } else {
+            designation.to_string()
+        }
+    }
+}
+
+/// Reads two ASCII digit bytes starting at `*idx` and advances `*idx` past them, returning their
+/// value as a two-digit number. Returns `None` (without advancing) if either byte is missing or
+/// not an ASCII digit.
+#[inline]
+fn parse_two_digits(bytes: &[u8], idx: &mut usize) -> Option<i32> {
+    let tens = *bytes.get(*idx)?;
+    let ones = *bytes.get(*idx + 1)?;
+    if !tens.is_ascii_digit() || !ones.is_ascii_digit() {
+        return None;
+    }
+    *idx += 2;
+    Some(i32::from(tens - b'0') * 10 + i32::from(ones - b'0'))
+}
+
+/// If the byte at `*idx` is `:`, advances `*idx` past it. This makes the `:` separators in
+/// `HH:MM:SS` optional so the same walk also accepts the compact `HHMMSS` form.
+#[inline]
+fn skip_colon(bytes: &[u8], idx: &mut usize) {
+    if bytes.get(*idx) == Some(&b':') {
+        *idx += 1;
+    }
+}
+
+/// Parses a `[+-]HH[:]MM[:]SS` offset string into a signed offset in seconds, walking the input
+/// byte-by-byte rather than allocating or depending on `regex`. Minutes and seconds default to
+/// zero when absent, and minute/second fields of 60 or greater are rejected.
+///
+/// This is the same grammar ICU4X's `GmtOffset` decodes directly from `[u8; 2]` digit pairs,
+/// which keeps this parser usable in allocation-averse contexts.
+#[inline]
+fn parse_offset_seconds(input: &str) -> Option<i32> {
+    let bytes = input.as_bytes();
+    let mut idx = 0;
+
+    let sign = match bytes.first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    idx += 1;
+
+    let hours = parse_two_digits(bytes, &mut idx)?;
+
+    skip_colon(bytes, &mut idx);
+    let minutes = if idx < bytes.len() {
+        parse_two_digits(bytes, &mut idx)?
+    } else {
+        0
+    };
+
+    skip_colon(bytes, &mut idx);
+    let seconds = if idx < bytes.len() {
+        parse_two_digits(bytes, &mut idx)?
+    } else {
+        0
+    };
+
+    if idx != bytes.len() || minutes > 59 || seconds > 59 {
+        return None;
+    }
+
+    Some(sign * (hours * SECONDS_IN_HOUR + minutes * SECONDS_IN_MINUTE + seconds))
 }
 
 impl TryFrom<&str> for Offset {
@@ -136,16 +344,25 @@ impl TryFrom<&str> for Offset {
     ///
     /// Accepts:
     ///
-    /// - `[+/-]HH[:]MM`
+    /// - `[+/-]HH`
+    /// - `[+/-]HH:MM`
+    /// - `[+/-]HHMM`
+    /// - `[+/-]HH:MM:SS`
+    /// - `[+/-]HHMMSS`
     /// - A-I representing +01:00 to +09:00
     /// - K-M representing +10:00 to +12:00
     /// - N-Y representing -01:00 to -12:00
     /// - Z representing 0 offset
+    /// - `-0000`/`-00:00` representing the RFC 2822 "unknown local offset" (see [`Offset::Unknown`])
     ///
     /// [accepted MRI values]: https://ruby-doc.org/core-2.6.3/Time.html#method-c-new
     #[inline]
     fn try_from(input: &str) -> Result<Self, Self::Error> {
         match input {
+            // The literal RFC 2822 "unknown local offset" form must be matched before the
+            // generic fixed-offset parser below, which would otherwise collapse it into an
+            // ordinary zero `Fixed` offset indistinguishable from `+00:00`.
+            "-0000" | "-00:00" => Ok(Self::unknown()),
             "A" => Ok(Self::fixed(1)),
             "B" => Ok(Self::fixed(2)),
             "C" => Ok(Self::fixed(3)),
@@ -171,23 +388,12 @@ impl TryFrom<&str> for Offset {
             "X" => Ok(Self::fixed(-11)),
             "Y" => Ok(Self::fixed(-12)),
             "Z" | "UTC" => Ok(Self::utc()),
-            _ => {
-                lazy_static! {
-                    static ref HH_MM_MATCHER: Regex = Regex::new(r"^([\-\+]{1})(\d{2})(\d{2})$").unwrap();
-                }
-                if HH_MM_MATCHER.is_match(input) {
-                    let caps = HH_MM_MATCHER.captures(input).unwrap();
-
-                    let sign = if caps.get(1).unwrap().as_str() == "+" { 1 } else { -1 };
-                    let hours = caps.get(2).unwrap().as_str().parse::<i32>().unwrap();
-                    let minutes = caps.get(3).unwrap().as_str().parse::<i32>().unwrap();
-
-                    let offset_seconds: i32 = sign * ((hours * SECONDS_IN_HOUR) + (minutes * SECONDS_IN_MINUTE));
-                    Ok(Self::fixed(offset_seconds))
-                } else {
-                    Err(TzStringError(input.to_string()))
+            _ => match parse_offset_seconds(input) {
+                Some(offset_seconds) => {
+                    Self::try_fixed(offset_seconds).map_err(|_| TzStringError(input.to_string()))
                 }
-            }
+                None => Err(TzStringError(input.to_string())),
+            },
         }
     }
 }
@@ -230,6 +436,7 @@ mod tests {
     fn offset_name(offset: &Offset) -> &str {
         match offset {
             Offset::Utc => "UTC",
+            Offset::Unknown => "-0000",
             Offset::Fixed(ltt) => ltt[0].time_zone_designation(),
             Offset::Tz(_) => "Ambiguous timezone name",
         }
@@ -250,21 +457,50 @@ mod tests {
     #[test]
     fn from_str_hh_mm() {
         assert_eq!(0, offset_seconds_from_fixed_offset("+0000"));
-        assert_eq!(0, offset_seconds_from_fixed_offset("-0000"));
         assert_eq!(60, offset_seconds_from_fixed_offset("+0001"));
         assert_eq!(-60, offset_seconds_from_fixed_offset("-0001"));
         assert_eq!(3600, offset_seconds_from_fixed_offset("+0100"));
         assert_eq!(-3600, offset_seconds_from_fixed_offset("-0100"));
         assert_eq!(7320, offset_seconds_from_fixed_offset("+0202"));
         assert_eq!(-7320, offset_seconds_from_fixed_offset("-0202"));
-        assert_eq!(362_340, offset_seconds_from_fixed_offset("+9999"));
-        assert_eq!(-362_340, offset_seconds_from_fixed_offset("-9999"));
-        assert_eq!(3660, offset_seconds_from_fixed_offset("+0061"));
     }
 
     #[test]
-    fn from_str_hh_mm_strange() {
-        assert_eq!(3660, offset_seconds_from_fixed_offset("+0061"));
+    fn from_str_out_of_range_hours_is_err() {
+        // 99 hours is well beyond the +/-23:59:59 range.
+        assert!(Offset::try_from("+9999").is_err());
+        assert!(Offset::try_from("-9999").is_err());
+    }
+
+    #[test]
+    fn from_str_out_of_range_minutes_is_err() {
+        // 61 minutes is not a valid minutes field, even though the total is in range.
+        assert!(Offset::try_from("+0061").is_err());
+    }
+
+    #[test]
+    fn from_str_hh_only() {
+        assert_eq!(3600, offset_seconds_from_fixed_offset("+01"));
+        assert_eq!(-3600, offset_seconds_from_fixed_offset("-01"));
+    }
+
+    #[test]
+    fn from_str_hh_colon_mm() {
+        assert_eq!(3600, offset_seconds_from_fixed_offset("+01:00"));
+        assert_eq!(5400, offset_seconds_from_fixed_offset("+01:30"));
+        assert_eq!(-3600, offset_seconds_from_fixed_offset("-01:00"));
+    }
+
+    #[test]
+    fn from_str_hh_colon_mm_colon_ss() {
+        assert_eq!(5415, offset_seconds_from_fixed_offset("+01:30:15"));
+        assert_eq!(-5415, offset_seconds_from_fixed_offset("-01:30:15"));
+    }
+
+    #[test]
+    fn from_str_hhmmss() {
+        assert_eq!(5415, offset_seconds_from_fixed_offset("+013015"));
+        assert_eq!(-5415, offset_seconds_from_fixed_offset("-013015"));
     }
 
     #[test]
@@ -283,7 +519,87 @@ mod tests {
         // Unexpected cases
         assert_eq!("-0000", offset_name(&Offset::from(-59)));
 
-        // FIXME: Should error instead
+        // `From<i32>`/`fixed` are infallible and do not validate range; `try_fixed` is the
+        // checked alternative for callers that can handle a `TzStringError`.
         assert_eq!("+10000", offset_name(&Offset::from(360_000)));
+        assert!(Offset::try_fixed(360_000).is_err());
+    }
+
+    #[test]
+    fn to_hhmm_formats_compact() {
+        let offset = Offset::try_fixed(5 * SECONDS_IN_HOUR + 30 * SECONDS_IN_MINUTE + 15).unwrap();
+        assert_eq!("+0530", offset.to_hhmm(0));
+    }
+
+    #[test]
+    fn to_hh_colon_mm_formats_with_colon() {
+        let offset = Offset::try_fixed(5 * SECONDS_IN_HOUR + 30 * SECONDS_IN_MINUTE).unwrap();
+        assert_eq!("+05:30", offset.to_hh_colon_mm(0));
+    }
+
+    #[test]
+    fn to_hh_mm_ss_preserves_seconds() {
+        let offset = Offset::try_fixed(5 * SECONDS_IN_HOUR + 30 * SECONDS_IN_MINUTE + 15).unwrap();
+        assert_eq!("+05:30:15", offset.to_hh_mm_ss(0));
+        assert_eq!("+0530", offset.to_hhmm(0));
+    }
+
+    #[test]
+    fn designation_defaults_to_offset_for_fixed() {
+        let offset = Offset::try_fixed(3600).unwrap();
+        assert_eq!("+0100", offset.designation(0));
+    }
+
+    #[test]
+    fn designation_is_utc_for_utc() {
+        assert_eq!("UTC", Offset::utc().designation(0));
+    }
+
+    #[test]
+    fn unknown_offset_is_distinct_from_utc_and_fixed_zero() {
+        let unknown = Offset::try_from("-0000").unwrap();
+        assert!(matches!(unknown, Offset::Unknown));
+        assert_ne!(unknown, Offset::Utc);
+        assert_ne!(unknown, Offset::from(0));
+
+        let unknown_colon = Offset::try_from("-00:00").unwrap();
+        assert_eq!(unknown, unknown_colon);
+    }
+
+    #[test]
+    fn unknown_offset_round_trips_to_negative_zero() {
+        let unknown = Offset::unknown();
+        assert_eq!("-0000", unknown.to_hhmm(0));
+        assert_eq!("-00:00", unknown.to_hh_colon_mm(0));
+        assert_eq!("-00:00:00", unknown.to_hh_mm_ss(0));
+        assert_eq!("-0000", unknown.designation(0));
+    }
+
+    #[test]
+    fn positive_zero_offset_is_not_unknown() {
+        assert_ne!(Offset::try_from("+0000").unwrap(), Offset::unknown());
+    }
+
+    #[test]
+    fn byte_parser_rejects_non_digit_and_truncated_input() {
+        assert!(Offset::try_from("+0a00").is_err());
+        assert!(Offset::try_from("+1").is_err());
+        assert!(Offset::try_from("+").is_err());
+        assert!(Offset::try_from("").is_err());
+        assert!(Offset::try_from("0100").is_err());
+    }
+
+    #[test]
+    fn byte_parser_rejects_trailing_garbage() {
+        assert!(Offset::try_from("+0100extra").is_err());
+        assert!(Offset::try_from("+01:00:00Z").is_err());
+    }
+
+    #[test]
+    fn utc_offset_seconds_matches_fixed_offset() {
+        let offset = Offset::try_fixed(5 * SECONDS_IN_HOUR + 30 * SECONDS_IN_MINUTE).unwrap();
+        assert_eq!(5 * SECONDS_IN_HOUR + 30 * SECONDS_IN_MINUTE, offset.utc_offset_seconds(0));
+        assert_eq!(0, Offset::utc().utc_offset_seconds(0));
+        assert_eq!(0, Offset::unknown().utc_offset_seconds(0));
     }
 }